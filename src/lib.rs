@@ -20,11 +20,17 @@ use nom::{
     bytes::complete::{tag, take, take_while, take_while1},
     character::complete::{char, digit0, i64, u64},
     multi::many1,
-    combinator::{recognize, complete, map},
+    combinator::{recognize, complete, map, map_res},
+    error::{Error as NomError, ErrorKind, ParseError},
     sequence::{terminated, tuple, delimited, pair, preceded},
-    Err, IResult, ParseTo,
+    Err, IResult, Offset, ParseTo,
 };
 use std::collections::BTreeMap;
+use std::ops::Range;
+use std::str::Utf8Error;
+
+#[cfg(feature = "bigint")]
+use num_bigint::BigInt;
 
 extern crate derive_more;
 
@@ -74,7 +80,7 @@ pub fn parse_bencode_string(input: &[u8]) -> IResult<&[u8], &[u8]> {
 /// # Note
 /// Although the functions are exposed directly, it's unsuitable to be used directly in most cases,
 /// it's provided for quick and dirty convenience only.
-pub fn parse_bencode_list(input: &[u8]) -> IResult<&[u8], Vec<BencodeItemView>> {
+pub fn parse_bencode_list(input: &[u8]) -> IResult<&[u8], Vec<BencodeItemView<'_>>> {
     let list_elems = many1(bencode_value);
 
     delimited(tag("l"), list_elems, tag("e"))(input)
@@ -84,7 +90,7 @@ pub fn parse_bencode_list(input: &[u8]) -> IResult<&[u8], Vec<BencodeItemView>>
 /// Main entry for the parser (for all practical purposes, a blob of bencode is consist of key value
 /// pairs). It parses out a bencode dictionary, bencode places no restriction on the homogeneity of
 /// dictionary pairs.
-pub fn parse_bencode_dict(input: &[u8]) -> IResult<&[u8], BTreeMap<&[u8], BencodeItemView>> {
+pub fn parse_bencode_dict(input: &[u8]) -> IResult<&[u8], BTreeMap<&[u8], BencodeItemView<'_>>> {
     let key_value = many1(pair(parse_bencode_string, bencode_value));
 
     let (remaining, key_value_pairs) = delimited(tag("d"), key_value, tag("e"))(input)?;
@@ -97,31 +103,171 @@ pub fn parse_bencode_dict(input: &[u8]) -> IResult<&[u8], BTreeMap<&[u8], Bencod
         });
 
     Ok((remaining, dict))
+}
+
+/// The ways [`parse_bencode_dict_strict`] can reject a blob: either it is well-formed bencode that
+/// still violates the stricter schema rules, or it isn't well-formed bencode at all.
+#[derive(Debug)]
+pub enum BencodeSchemaErrorKinds {
+    /// A dictionary's keys were not in ascending lexicographical byte order.
+    DictNotInLexicographicalOrder,
+
+    /// The same dictionary key appeared more than once.
+    DuplicateDictKey,
+
+    /// Wraps an ordinary nom parsing failure (e.g. a malformed integer or truncated string).
+    NomInternal(ErrorKind),
+}
+
+/// Error type for [`parse_bencode_dict_strict`], carrying a human-readable message alongside the
+/// structured [`BencodeSchemaErrorKinds`].
+#[derive(Debug)]
+pub struct BencodeSchemaError {
+    message: String,
+    kind: BencodeSchemaErrorKinds,
+}
+
+impl BencodeSchemaError {
+    pub fn new(message: String, kind: BencodeSchemaErrorKinds) -> Self {
+        Self { message, kind }
+    }
+
+    pub fn kind(&self) -> &BencodeSchemaErrorKinds {
+        &self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl<'a> ParseError<&'a [u8]> for BencodeSchemaError {
+    fn from_error_kind(input: &'a [u8], kind: ErrorKind) -> Self {
+        Self::new(format!("{:?}:\t{:?}\n", kind, input), BencodeSchemaErrorKinds::NomInternal(kind))
+    }
+
+    fn append(input: &'a [u8], kind: ErrorKind, other: Self) -> Self {
+        Self::new(
+            format!("{}{:?}:\t{:?}\n", other.message, kind, input),
+            BencodeSchemaErrorKinds::NomInternal(kind),
+        )
+    }
+}
+
+impl<'a> From<NomError<&'a [u8]>> for BencodeSchemaError {
+    fn from(err: NomError<&'a [u8]>) -> Self {
+        Self::from_error_kind(err.input, err.code)
+    }
+}
+
+impl<'a> nom::error::FromExternalError<&'a [u8], ()> for BencodeSchemaError {
+    fn from_external_error(input: &'a [u8], kind: ErrorKind, _e: ()) -> Self {
+        Self::from_error_kind(input, kind)
+    }
+}
+
+/// Re-runs [`parse_bencode_string`], translating its ordinary nom error into a
+/// [`BencodeSchemaError`] so it can be composed with the rest of the strict parsers below.
+fn parse_bencode_string_strict(input: &[u8]) -> IResult<&[u8], &[u8], BencodeSchemaError> {
+    parse_bencode_string(input).map_err(|e| e.map(BencodeSchemaError::from))
+}
+
+/// Same as [`parse_bencode_string_strict`], but for the literal tag bytes (`d`/`l`/`e`) the strict
+/// combinators delimit on.
+fn strict_tag<'a>(literal: &'static str, input: &'a [u8]) -> IResult<&'a [u8], &'a [u8], BencodeSchemaError> {
+    tag(literal)(input).map_err(|e: Err<NomError<&'a [u8]>>| e.map(BencodeSchemaError::from))
+}
+
+/// Strict counterpart to [`bencode_value`], recursing into itself and [`parse_bencode_dict_strict`]
+/// instead of the lenient parsers so nested dicts get checked too.
+fn bencode_value_strict(input: &[u8]) -> IResult<&[u8], BencodeItemView<'_>, BencodeSchemaError> {
+    let to_int = map_res(
+        |i| parse_bencode_num(i).map_err(|e| e.map(BencodeSchemaError::from)),
+        int_pattern_to_view,
+    );
+    let to_byte_str = map(parse_bencode_string_strict, BencodeItemView::ByteString);
+    let to_list = map(
+        delimited(
+            |i| strict_tag("l", i),
+            many1(bencode_value_strict),
+            |i| strict_tag("e", i),
+        ),
+        BencodeItemView::List,
+    );
+    let to_dict = map(parse_bencode_dict_strict, BencodeItemView::Dictionary);
+
+    alt((to_int, to_byte_str, to_list, to_dict))(input)
+}
+
+/// Same as [`parse_bencode_dict`], but rejects dictionaries whose keys aren't in strictly ascending
+/// lexicographical byte order, at any depth (nested values go through `bencode_value_strict`
+/// instead of the lenient `bencode_value`). Returns `Err::Failure` with
+/// [`BencodeSchemaErrorKinds::DictNotInLexicographicalOrder`] or
+/// [`BencodeSchemaErrorKinds::DuplicateDictKey`] as appropriate.
+pub fn parse_bencode_dict_strict(
+    input: &[u8],
+) -> IResult<&[u8], BTreeMap<&[u8], BencodeItemView<'_>>, BencodeSchemaError> {
+    let key_value = many1(pair(parse_bencode_string_strict, bencode_value_strict));
+
+    let (remaining, key_value_pairs) = delimited(
+        |i| strict_tag("d", i),
+        key_value,
+        |i| strict_tag("e", i),
+    )(input)?;
+
+    for window in key_value_pairs.windows(2) {
+        let (prev_key, _) = window[0];
+        let (curr_key, _) = window[1];
+
+        match prev_key.cmp(curr_key) {
+            std::cmp::Ordering::Less => {}
+            std::cmp::Ordering::Equal => {
+                return Err(Err::Failure(BencodeSchemaError::new(
+                    format!("duplicate dictionary key: {:?}", curr_key),
+                    BencodeSchemaErrorKinds::DuplicateDictKey,
+                )));
+            }
+            std::cmp::Ordering::Greater => {
+                return Err(Err::Failure(BencodeSchemaError::new(
+                    format!(
+                        "dictionary keys not in lexicographical order: {:?} appears after {:?}",
+                        curr_key, prev_key
+                    ),
+                    BencodeSchemaErrorKinds::DictNotInLexicographicalOrder,
+                )));
+            }
+        }
+    }
+
+    let dict = key_value_pairs.into_iter().collect::<BTreeMap<_, _>>();
+
+    Ok((remaining, dict))
+}
+
+/// Converts the validated digit slice produced by [`parse_bencode_num`] into an integer
+/// `BencodeItemView`. The fast path fits the digits into an `i64`; if they don't fit, the `bigint`
+/// feature falls back to an arbitrary-precision integer, while without it the value simply fails to
+/// parse rather than panicking.
+fn int_pattern_to_view(int_pattern: &[u8]) -> Result<BencodeItemView<'_>, ()> {
+    if let Some(n) = int_pattern.parse_to() {
+        return Ok(BencodeItemView::Integer(n));
+    }
+
+    #[cfg(feature = "bigint")]
+    {
+        let digits = std::str::from_utf8(int_pattern).map_err(|_| ())?;
+        digits.parse::<BigInt>().map(BencodeItemView::BigInteger).map_err(|_| ())
+    }
 
-    // TODO: bencode requires the keys of the dictionary to be in lexicographical order, maybe this
-    // isn't the best place to handle this
-    //
-    // // somehow the Vec::is_sorted requires nightly as of 1.61, this is so ghetto
-    //
-    // let mut sorted = key_value_pairs.clone();
-    // sorted.sort_unstable_by_key(|elem| elem.0);
-    // let sorted_keys = sorted.iter().map(|x| x.0);
-    //
-    //
-    // if !key_value_pairs
-    //     .iter()
-    //     .map(|x| x.0)
-    //     .zip(sorted_keys)
-    //     .all(|pair| pair.0 == pair.1) {
-    //     return Err::Failure(BencodeSchemaError::new("Nothing is actually broken about your dict, but the bencode specification states all keys must appear in lexicographical order".into_string(), BencodeSchemaErrorKinds::DictNotInLexicographicalOrder));
-    // }
+    #[cfg(not(feature = "bigint"))]
+    {
+        Err(())
+    }
 }
 
 /// Top level combinator for choosing an appreciate strategy for parsing out a bencode item
-fn bencode_value(input: &[u8]) -> IResult<&[u8], BencodeItemView> {
-    let to_int = map(parse_bencode_num, |int_pattern| {
-        BencodeItemView::Integer(int_pattern.parse_to().unwrap())
-    });
+fn bencode_value(input: &[u8]) -> IResult<&[u8], BencodeItemView<'_>> {
+    let to_int = map_res(parse_bencode_num, int_pattern_to_view);
     let to_byte_str = map(parse_bencode_string, |byte_slice| {
         BencodeItemView::ByteString(byte_slice)
     });
@@ -131,6 +277,100 @@ fn bencode_value(input: &[u8]) -> IResult<&[u8], BencodeItemView> {
     alt((to_int, to_byte_str, to_list, to_dict))(input)
 }
 
+/// Entry point for parsing a bencode blob while recording, for every node, the exact byte range it
+/// occupied in `input`. A core use of bencode is computing a torrent's info-hash: the digest of the
+/// *exact original bytes* stored under the `info` key, which re-encoding can't recover, since any
+/// normalization (e.g. dict key re-sorting) would change the digest. With the span of the `info`
+/// value in hand, callers can slice `input` directly and hash it.
+pub fn parse_bencode_with_span(input: &[u8]) -> IResult<&[u8], BencodeItemViewWithSpan<'_>> {
+    bencode_value_spanned(input, input)
+}
+
+/// Same as [`bencode_value`], but also records the `(start, end)` byte range of every node relative
+/// to `original`, computed from how much of `original` each sub-parser consumed. Built on the same
+/// combinators `bencode_value` uses, recursing into itself for child values.
+fn bencode_value_spanned<'a>(
+    original: &'a [u8],
+    input: &'a [u8],
+) -> IResult<&'a [u8], BencodeItemViewWithSpan<'a>> {
+    let start = original.offset(input);
+
+    if let Ok((remaining, int_pattern)) = parse_bencode_num(input) {
+        let end = original.offset(remaining);
+        return match int_pattern_to_view(int_pattern) {
+            Ok(BencodeItemView::Integer(n)) => Ok((remaining, BencodeItemViewWithSpan::Integer(n, start..end))),
+            #[cfg(feature = "bigint")]
+            Ok(BencodeItemView::BigInteger(n)) => {
+                Ok((remaining, BencodeItemViewWithSpan::BigInteger(n, start..end)))
+            }
+            _ => Err(Err::Error(NomError::new(input, ErrorKind::MapRes))),
+        };
+    }
+
+    if let Ok((remaining, bytes)) = parse_bencode_string(input) {
+        let end = original.offset(remaining);
+        return Ok((remaining, BencodeItemViewWithSpan::ByteString(bytes, start..end)));
+    }
+
+    if let Ok((remaining, items)) =
+        delimited(tag("l"), many1(|i| bencode_value_spanned(original, i)), tag("e"))(input)
+    {
+        let end = original.offset(remaining);
+        return Ok((remaining, BencodeItemViewWithSpan::List(items, start..end)));
+    }
+
+    let (remaining, pairs) = delimited(
+        tag("d"),
+        many1(pair(parse_bencode_string, |i| bencode_value_spanned(original, i))),
+        tag("e"),
+    )(input)?;
+    let end = original.offset(remaining);
+    let dict = pairs.into_iter().collect::<BTreeMap<_, _>>();
+    Ok((remaining, BencodeItemViewWithSpan::Dictionary(dict, start..end)))
+}
+
+/// Parallel tree to [`BencodeItemView`] that additionally carries, for every node, the `Range<usize>`
+/// of bytes it occupied in the buffer originally passed to [`parse_bencode_with_span`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BencodeItemViewWithSpan<'a> {
+    Integer(i64, Range<usize>),
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt, Range<usize>),
+    ByteString(&'a [u8], Range<usize>),
+    List(Vec<BencodeItemViewWithSpan<'a>>, Range<usize>),
+    Dictionary(BTreeMap<&'a [u8], BencodeItemViewWithSpan<'a>>, Range<usize>),
+}
+
+impl<'a> BencodeItemViewWithSpan<'a> {
+    /// The byte range this node occupied in the buffer it was parsed from.
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            BencodeItemViewWithSpan::Integer(_, span)
+            | BencodeItemViewWithSpan::ByteString(_, span)
+            | BencodeItemViewWithSpan::List(_, span)
+            | BencodeItemViewWithSpan::Dictionary(_, span) => span.clone(),
+            #[cfg(feature = "bigint")]
+            BencodeItemViewWithSpan::BigInteger(_, span) => span.clone(),
+        }
+    }
+
+    /// Strips span information, producing the plain [`BencodeItemView`] tree.
+    pub fn view(&self) -> BencodeItemView<'a> {
+        match self {
+            BencodeItemViewWithSpan::Integer(n, _) => BencodeItemView::Integer(*n),
+            #[cfg(feature = "bigint")]
+            BencodeItemViewWithSpan::BigInteger(n, _) => BencodeItemView::BigInteger(n.clone()),
+            BencodeItemViewWithSpan::ByteString(bytes, _) => BencodeItemView::ByteString(bytes),
+            BencodeItemViewWithSpan::List(items, _) => {
+                BencodeItemView::List(items.iter().map(BencodeItemViewWithSpan::view).collect())
+            }
+            BencodeItemViewWithSpan::Dictionary(dict, _) => BencodeItemView::Dictionary(
+                dict.iter().map(|(key, value)| (*key, value.view())).collect(),
+            ),
+        }
+    }
+}
+
 /// Representation of bencode blobs as a tree. The lifetime is tied to the text in memory, achieving
 /// *almost zero copy*. This is perhaps unsuitable for large bencode blobs since the entire blob may
 /// not fit inside the memory.
@@ -144,6 +384,11 @@ pub enum BencodeItemView<'a> {
     /// since no range limit is specified in the bencode document.
     Integer(i64),
 
+    /// An integer outside the range of `i64`. Only produced when the `bigint` feature is enabled;
+    /// without it, such integers simply fail to parse.
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+
     /// Bencode strings are not guaranteed to be UTF-8, thus using a byte slice
     ByteString(&'a [u8]),
 
@@ -155,6 +400,226 @@ pub enum BencodeItemView<'a> {
     Dictionary(BTreeMap<&'a [u8], BencodeItemView<'a>>),
 }
 
+impl<'a> BencodeItemView<'a> {
+    /// Returns the wrapped integer, or `None` if this isn't a [`BencodeItemView::Integer`]. Note
+    /// that a `BigInteger` (the `bigint` feature's overflow fallback) is not an `Integer` and so
+    /// also yields `None` here; use `bigint()` for that case.
+    pub fn int(&self) -> Option<i64> {
+        match self {
+            BencodeItemView::Integer(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped arbitrary-precision integer, or `None` if this isn't a
+    /// [`BencodeItemView::BigInteger`]. Only available with the `bigint` feature enabled.
+    #[cfg(feature = "bigint")]
+    pub fn bigint(&self) -> Option<&BigInt> {
+        match self {
+            BencodeItemView::BigInteger(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped byte string, or `None` if this isn't a [`BencodeItemView::ByteString`].
+    pub fn bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            BencodeItemView::ByteString(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped byte string interpreted as UTF-8, or `None` if this isn't a
+    /// [`BencodeItemView::ByteString`]. Bencode strings aren't guaranteed to be UTF-8, so the
+    /// conversion itself can still fail, hence the nested `Result`.
+    pub fn str(&self) -> Option<Result<&'a str, Utf8Error>> {
+        self.bytes().map(std::str::from_utf8)
+    }
+
+    /// Returns the wrapped list, or `None` if this isn't a [`BencodeItemView::List`].
+    pub fn list(&self) -> Option<&[BencodeItemView<'a>]> {
+        match self {
+            BencodeItemView::List(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Returns the wrapped dictionary, or `None` if this isn't a [`BencodeItemView::Dictionary`].
+    pub fn dict(&self) -> Option<&BTreeMap<&'a [u8], BencodeItemView<'a>>> {
+        match self {
+            BencodeItemView::Dictionary(dict) => Some(dict),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` in this value, if it's a dictionary. Returns `None` if this isn't a
+    /// dictionary, or the dictionary has no such key.
+    pub fn get(&self, key: &[u8]) -> Option<&BencodeItemView<'a>> {
+        self.dict()?.get(key)
+    }
+
+    /// Walks a path of dictionary keys, descending one level per key. Returns `None` as soon as a
+    /// step isn't a dictionary, or is missing the next key in the path.
+    pub fn get_path(&self, path: &[&[u8]]) -> Option<&BencodeItemView<'a>> {
+        let mut current = self;
+        for key in path {
+            current = current.get(key)?;
+        }
+        Some(current)
+    }
+
+    /// Serialize this value back into its bencode byte representation. This is the dual of
+    /// `bencode_value` (and friends): parsing and then encoding a well-formed blob round-trips,
+    /// modulo dictionary key order, which is always normalized to ascending lexicographical order.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            BencodeItemView::Integer(n) => n.to_bencode(),
+            #[cfg(feature = "bigint")]
+            BencodeItemView::BigInteger(n) => format!("i{}e", n).into_bytes(),
+            BencodeItemView::ByteString(bytes) => bytes.to_bencode(),
+            BencodeItemView::List(items) => {
+                let mut out = vec![b'l'];
+                for item in items {
+                    out.extend(item.encode());
+                }
+                out.push(b'e');
+                out
+            }
+            BencodeItemView::Dictionary(dict) => {
+                let mut out = vec![b'd'];
+                // keys are already in ascending order courtesy of BTreeMap
+                for (key, value) in dict {
+                    out.extend(key.to_bencode());
+                    out.extend(value.encode());
+                }
+                out.push(b'e');
+                out
+            }
+        }
+    }
+}
+
+/// Owned mirror of [`BencodeItemView`], detached from the lifetime of the buffer it was parsed out
+/// of. Parse with the borrowing, zero-copy [`BencodeItemView`] for speed, then convert to this type
+/// when the tree needs to outlive the source bytes (e.g. reading a file, parsing it, and returning
+/// the tree to a caller).
+#[derive(Debug, Ord, Clone, PartialOrd, Eq, PartialEq, Hash)]
+pub enum BencodeItem {
+    /// Bencode integers are represented as i64 for now, technically this is not to specification
+    /// since no range limit is specified in the bencode document.
+    Integer(i64),
+
+    /// An integer outside the range of `i64`. Only produced when the `bigint` feature is enabled.
+    #[cfg(feature = "bigint")]
+    BigInteger(BigInt),
+
+    /// Bencode strings are not guaranteed to be UTF-8, thus using a byte vector
+    ByteString(Vec<u8>),
+
+    /// Bencode lists, not lists may not be homogeneous
+    List(Vec<BencodeItem>),
+
+    /// Bencode dictionary, not lists may not be homogeneous. Bencode dictionary by specification
+    /// must be lexicographically sorted, BTree preserves ordering
+    Dictionary(BTreeMap<Vec<u8>, BencodeItem>),
+}
+
+impl<'a> BencodeItemView<'a> {
+    /// Detach this view from the buffer it borrows from, copying every byte string into an owned
+    /// [`BencodeItem`] tree.
+    pub fn to_owned(&self) -> BencodeItem {
+        match self {
+            BencodeItemView::Integer(n) => BencodeItem::Integer(*n),
+            #[cfg(feature = "bigint")]
+            BencodeItemView::BigInteger(n) => BencodeItem::BigInteger(n.clone()),
+            BencodeItemView::ByteString(bytes) => BencodeItem::ByteString(bytes.to_vec()),
+            BencodeItemView::List(items) => {
+                BencodeItem::List(items.iter().map(BencodeItemView::to_owned).collect())
+            }
+            BencodeItemView::Dictionary(dict) => BencodeItem::Dictionary(
+                dict.iter()
+                    .map(|(key, value)| (key.to_vec(), value.to_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl<'a> From<BencodeItemView<'a>> for BencodeItem {
+    fn from(view: BencodeItemView<'a>) -> Self {
+        view.to_owned()
+    }
+}
+
+impl<'a> From<&'a BencodeItem> for BencodeItemView<'a> {
+    fn from(item: &'a BencodeItem) -> Self {
+        match item {
+            BencodeItem::Integer(n) => BencodeItemView::Integer(*n),
+            #[cfg(feature = "bigint")]
+            BencodeItem::BigInteger(n) => BencodeItemView::BigInteger(n.clone()),
+            BencodeItem::ByteString(bytes) => BencodeItemView::ByteString(bytes.as_slice()),
+            BencodeItem::List(items) => {
+                BencodeItemView::List(items.iter().map(BencodeItemView::from).collect())
+            }
+            BencodeItem::Dictionary(dict) => BencodeItemView::Dictionary(
+                dict.iter()
+                    .map(|(key, value)| (key.as_slice(), BencodeItemView::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Converts a Rust value into its bencode byte representation, the dual of `parse_bencode_num` /
+/// `parse_bencode_string` / `parse_bencode_list` / `parse_bencode_dict`. Named after `bendy`'s trait
+/// of the same name.
+pub trait ToBencode {
+    fn to_bencode(&self) -> Vec<u8>;
+}
+
+impl ToBencode for i64 {
+    fn to_bencode(&self) -> Vec<u8> {
+        format!("i{}e", self).into_bytes()
+    }
+}
+
+impl ToBencode for &[u8] {
+    fn to_bencode(&self) -> Vec<u8> {
+        let mut out = self.len().to_string().into_bytes();
+        out.push(b':');
+        out.extend_from_slice(self);
+        out
+    }
+}
+
+impl<T: ToBencode> ToBencode for Vec<T> {
+    fn to_bencode(&self) -> Vec<u8> {
+        let mut out = vec![b'l'];
+        for item in self {
+            out.extend(item.to_bencode());
+        }
+        out.push(b'e');
+        out
+    }
+}
+
+impl<K: AsRef<[u8]>, V: ToBencode> ToBencode for BTreeMap<K, V> {
+    fn to_bencode(&self) -> Vec<u8> {
+        // BTreeMap's own ordering is keyed on `K`'s `Ord` impl, which isn't guaranteed to agree with
+        // raw byte lexicographical order (e.g. a `K` with a custom `Ord`), so sort explicitly.
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|(a, _), (b, _)| a.as_ref().cmp(b.as_ref()));
+
+        let mut out = vec![b'd'];
+        for (key, value) in entries {
+            out.extend(key.as_ref().to_bencode());
+            out.extend(value.to_bencode());
+        }
+        out.push(b'e');
+        out
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,4 +725,252 @@ mod tests {
         assert_eq!(expected, parsed);
         assert_eq!(remaining, b"");
     }
+
+    #[test]
+    fn integer_encodes_to_i_n_e() {
+        assert_eq!(BencodeItemView::Integer(42).encode(), b"i42e");
+        assert_eq!(BencodeItemView::Integer(-7).encode(), b"i-7e");
+    }
+
+    #[test]
+    fn byte_string_encodes_with_length_prefix() {
+        assert_eq!(BencodeItemView::ByteString(b"spam").encode(), b"4:spam");
+    }
+
+    #[test]
+    fn list_encodes_between_l_and_e() {
+        let list = BencodeItemView::List(vec![
+            BencodeItemView::ByteString(b"spam"),
+            BencodeItemView::Integer(42),
+        ]);
+        assert_eq!(list.encode(), b"l4:spami42ee");
+    }
+
+    #[test]
+    fn dict_encodes_with_sorted_keys() {
+        let mut dict = BTreeMap::new();
+        dict.insert(b"foo".as_slice(), BencodeItemView::Integer(42));
+        dict.insert(b"bar".as_slice(), BencodeItemView::ByteString(b"spam"));
+        assert_eq!(
+            BencodeItemView::Dictionary(dict).encode(),
+            b"d3:bar4:spam3:fooi42ee"
+        );
+    }
+
+    #[test]
+    fn parse_then_encode_round_trips() {
+        let input = b"d3:bar4:spam3:fooi42ee";
+        let (_, parsed) = parse_bencode_dict(input).unwrap();
+        assert_eq!(BencodeItemView::Dictionary(parsed).encode(), input);
+    }
+
+    #[test]
+    fn to_bencode_sorts_user_btreemap_keys_by_raw_bytes() {
+        let mut dict: BTreeMap<Vec<u8>, i64> = BTreeMap::new();
+        dict.insert(b"foo".to_vec(), 42);
+        dict.insert(b"bar".to_vec(), 1);
+        assert_eq!(dict.to_bencode(), b"d3:bari1e3:fooi42ee");
+    }
+
+    #[test]
+    fn view_to_owned_detaches_from_source_buffer() {
+        let owned = {
+            let input = b"l4:spami42ee".to_vec();
+            let (_, view) = parse_bencode_list(&input).unwrap();
+            BencodeItemView::List(view).to_owned()
+        };
+
+        assert_eq!(
+            owned,
+            BencodeItem::List(vec![
+                BencodeItem::ByteString(b"spam".to_vec()),
+                BencodeItem::Integer(42),
+            ])
+        );
+    }
+
+    #[test]
+    fn owned_dict_round_trips_through_view() {
+        let mut owned_dict = BTreeMap::new();
+        owned_dict.insert(b"bar".to_vec(), BencodeItem::ByteString(b"spam".to_vec()));
+        owned_dict.insert(b"foo".to_vec(), BencodeItem::Integer(42));
+        let owned = BencodeItem::Dictionary(owned_dict);
+
+        let view = BencodeItemView::from(&owned);
+        assert_eq!(view.to_owned(), owned);
+    }
+
+    #[test]
+    fn into_trait_matches_to_owned() {
+        let view = BencodeItemView::Integer(7);
+        let via_into: BencodeItem = view.clone().into();
+        assert_eq!(via_into, view.to_owned());
+    }
+
+    #[test]
+    fn typed_accessors_return_none_for_mismatched_variant() {
+        let view = BencodeItemView::Integer(42);
+        assert_eq!(view.int(), Some(42));
+        assert_eq!(view.bytes(), None);
+        assert!(view.str().is_none());
+        assert!(view.list().is_none());
+        assert!(view.dict().is_none());
+    }
+
+    #[test]
+    fn str_accessor_surfaces_invalid_utf8() {
+        let view = BencodeItemView::ByteString(&[0xff, 0xfe]);
+        assert!(view.str().unwrap().is_err());
+    }
+
+    #[test]
+    fn get_looks_up_dict_entry_by_key() {
+        let (_, parsed) = parse_bencode_dict(b"d3:bar4:spam3:fooi42ee").unwrap();
+        let view = BencodeItemView::Dictionary(parsed);
+
+        assert_eq!(view.get(b"foo").and_then(BencodeItemView::int), Some(42));
+        assert_eq!(
+            view.get(b"bar").and_then(BencodeItemView::bytes),
+            Some(b"spam".as_slice())
+        );
+        assert!(view.get(b"missing").is_none());
+    }
+
+    #[test]
+    fn get_path_walks_nested_dictionaries() {
+        let (_, parsed) = parse_bencode_dict(b"d4:infod6:lengthi1024eee").unwrap();
+        let view = BencodeItemView::Dictionary(parsed);
+
+        assert_eq!(
+            view.get_path(&[b"info", b"length"])
+                .and_then(BencodeItemView::int),
+            Some(1024)
+        );
+        assert!(view.get_path(&[b"info", b"missing"]).is_none());
+        assert!(view.get_path(&[b"missing", b"length"]).is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "bigint"))]
+    fn integer_overflowing_i64_fails_to_parse_without_bigint_feature() {
+        let overflowing = format!("i{}0e", i64::MAX);
+        let parsed = bencode_value(overflowing.as_bytes());
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn integer_overflowing_i64_falls_back_to_bigint() {
+        let overflowing = format!("i{}0e", i64::MAX);
+        let (_, parsed) = bencode_value(overflowing.as_bytes()).unwrap();
+        assert_eq!(
+            parsed.bigint(),
+            Some(&overflowing[1..overflowing.len() - 1].parse::<BigInt>().unwrap())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn bigint_round_trips_through_encode() {
+        let overflowing = format!("i{}0e", i64::MAX);
+        let (_, parsed) = bencode_value(overflowing.as_bytes()).unwrap();
+        assert_eq!(parsed.encode(), overflowing.as_bytes());
+    }
+
+    #[test]
+    fn strict_dict_accepts_sorted_keys() {
+        let (_, parsed) = parse_bencode_dict_strict(b"d3:bar4:spam3:fooi42ee").unwrap();
+        assert_eq!(parsed.get(b"bar".as_slice()).unwrap().bytes(), Some(b"spam".as_slice()));
+    }
+
+    #[test]
+    fn strict_dict_rejects_out_of_order_keys() {
+        let err = parse_bencode_dict_strict(b"d3:fooi42e3:bar4:spame").unwrap_err();
+        match err {
+            Err::Failure(e) => assert!(matches!(
+                e.kind(),
+                BencodeSchemaErrorKinds::DictNotInLexicographicalOrder
+            )),
+            other => panic!("expected Err::Failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn strict_dict_rejects_duplicate_keys() {
+        let err = parse_bencode_dict_strict(b"d3:fooi1e3:fooi2ee").unwrap_err();
+        match err {
+            Err::Failure(e) => assert!(matches!(e.kind(), BencodeSchemaErrorKinds::DuplicateDictKey)),
+            other => panic!("expected Err::Failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn lenient_dict_silently_accepts_out_of_order_keys() {
+        // Documents the gap `parse_bencode_dict_strict` closes: the lenient parser folds into a
+        // BTreeMap regardless of the input's declared key order.
+        assert!(parse_bencode_dict(b"d3:fooi42e3:bar4:spame").is_ok());
+    }
+
+    #[test]
+    fn strict_dict_rejects_out_of_order_keys_in_a_nested_dict() {
+        // The `info` dict of a real torrent is nested one level down; the guarantee has to hold
+        // there too, not just for the outermost dict.
+        let err = parse_bencode_dict_strict(b"d4:infod3:fooi1e3:bari2eeee").unwrap_err();
+        match err {
+            Err::Failure(e) => assert!(matches!(
+                e.kind(),
+                BencodeSchemaErrorKinds::DictNotInLexicographicalOrder
+            )),
+            other => panic!("expected Err::Failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn strict_dict_rejects_out_of_order_keys_nested_inside_a_list() {
+        let err = parse_bencode_dict_strict(b"d4:listld3:fooi1e3:bari2eeee").unwrap_err();
+        match err {
+            Err::Failure(e) => assert!(matches!(
+                e.kind(),
+                BencodeSchemaErrorKinds::DictNotInLexicographicalOrder
+            )),
+            other => panic!("expected Err::Failure, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn span_of_whole_value_covers_the_entire_input() {
+        let input = b"i42e";
+        let (_, parsed) = parse_bencode_with_span(input).unwrap();
+        assert_eq!(parsed.span(), 0..input.len());
+    }
+
+    #[test]
+    fn span_rejects_empty_lists_and_dicts_like_the_lenient_parsers_do() {
+        // `parse_bencode_list`/`parse_bencode_dict` are built on `many1`, requiring at least one
+        // element; the span-tracking parser must reject the same inputs for the same reason.
+        assert!(parse_bencode_with_span(b"le").is_err());
+        assert!(parse_bencode_with_span(b"de").is_err());
+        assert!(parse_bencode_list(b"le").is_err());
+        assert!(parse_bencode_dict(b"de").is_err());
+    }
+
+    #[test]
+    fn span_of_nested_value_is_relative_to_the_outer_buffer() {
+        let input = b"d4:infod6:lengthi1024eee";
+        let (_, parsed) = parse_bencode_with_span(input).unwrap();
+
+        let info_span = match &parsed {
+            BencodeItemViewWithSpan::Dictionary(dict, _) => dict[b"info".as_slice()].span(),
+            other => panic!("expected a dictionary, got {:?}", other),
+        };
+        assert_eq!(&input[info_span], b"d6:lengthi1024ee".as_slice());
+    }
+
+    #[test]
+    fn with_span_view_strips_down_to_plain_bencode_item_view() {
+        let input = b"l4:spami42ee";
+        let (_, parsed) = parse_bencode_with_span(input).unwrap();
+        let (_, expected) = parse_bencode_list(input).unwrap();
+        assert_eq!(parsed.view(), BencodeItemView::List(expected));
+    }
 }